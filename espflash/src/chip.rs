@@ -0,0 +1,54 @@
+use crate::error::ChipDetectError;
+use crate::image_format::bl602::{is_bl602, BL602_MAGIC};
+use crate::image_format::ImageFormatId;
+use std::fmt::{Display, Formatter};
+use strum::AsStaticStr;
+
+/// A chip supported by the flashing workflow.
+///
+/// Originally Espressif-only; [`Chip::Bl602`] generalises the same
+/// write/verify path to the Bouffalo BL602 the way `blflash` does.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, AsStaticStr)]
+pub enum Chip {
+    #[strum(serialize = "esp8266")]
+    Esp8266,
+    #[strum(serialize = "esp32")]
+    Esp32,
+    #[strum(serialize = "esp32-c3")]
+    Esp32c3,
+    #[strum(serialize = "bl602")]
+    Bl602,
+}
+
+impl Chip {
+    /// The image formats this chip can be flashed with.
+    pub fn supported_image_formats(&self) -> &'static [ImageFormatId] {
+        match self {
+            Chip::Esp8266 => &[ImageFormatId::Bootloader],
+            Chip::Esp32 => &[ImageFormatId::Bootloader],
+            Chip::Esp32c3 => &[ImageFormatId::Bootloader, ImageFormatId::DirectBoot],
+            Chip::Bl602 => &[ImageFormatId::Bl602],
+        }
+    }
+
+    /// Identify a chip from the magic word reported at connect time.
+    pub fn from_magic(magic: u32) -> Result<Chip, ChipDetectError> {
+        match magic {
+            0x00f0_1d83 => Ok(Chip::Esp8266),
+            0x0000_0000 | 0x1500_0000 => Ok(Chip::Esp32),
+            0x6921_506f | 0x1b31_506f => Ok(Chip::Esp32c3),
+            BL602_MAGIC => Ok(Chip::Bl602),
+            other => match is_bl602(other) {
+                Some(chip) => Ok(chip),
+                None => Err(ChipDetectError::from(other)),
+            },
+        }
+    }
+}
+
+impl Display for Chip {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use strum::AsStaticRef;
+        f.write_str(self.as_static())
+    }
+}
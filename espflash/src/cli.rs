@@ -0,0 +1,43 @@
+use crate::elf::RomSegment;
+use crate::error::Error;
+use crate::flasher::Flasher;
+use crate::verify;
+use structopt::StructOpt;
+
+/// Flashing options shared by the flash subcommands.
+#[derive(Debug, StructOpt)]
+pub struct FlashOpts {
+    /// Read each region back and compare a CRC-32 against what was written.
+    ///
+    /// Verification is on by default; pass `--no-verify` to skip the read-back
+    /// and trade integrity for speed.
+    #[structopt(long = "verify")]
+    verify: bool,
+    #[structopt(long = "no-verify", conflicts_with = "verify")]
+    no_verify: bool,
+}
+
+impl FlashOpts {
+    /// Whether the read-back verification stage should run.
+    pub fn verify_enabled(&self) -> bool {
+        !self.no_verify
+    }
+}
+
+/// Write every segment to flash, verifying each one if requested.
+///
+/// The per-segment CRC read-back runs immediately after the write so a faulty
+/// region is reported before moving on to the next.
+pub fn write_segments<'a>(
+    flasher: &mut Flasher,
+    segments: impl Iterator<Item = RomSegment<'a>>,
+    opts: &FlashOpts,
+) -> Result<(), Error> {
+    for segment in segments {
+        flasher.write_bin_to_flash(segment.addr, segment.data.as_ref())?;
+        if opts.verify_enabled() {
+            verify::verify_segment(flasher, &segment)?;
+        }
+    }
+    Ok(())
+}
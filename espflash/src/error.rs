@@ -33,7 +33,7 @@ pub enum Error {
     #[error("The bootloader returned an error")]
     #[diagnostic(transparent)]
     RomError(#[from] RomError),
-    #[error("Chip not recognized, supported chip types are esp8266, esp32 and esp32-c3")]
+    #[error("Chip not recognized, supported chip types are esp8266, esp32, esp32-c3 and bl602")]
     #[diagnostic(
         code(espflash::unrecognized_chip),
         help("If your chip is supported, try hard-resetting the device and try again")
@@ -66,6 +66,27 @@ https://github.com/espressif/esp32c3-direct-boot-example"
         )
     )]
     InvalidDirectBootBinary,
+    #[error("Flash content verification failed at {addr:#x}: wrote crc {expected_crc:#010x} but read back {actual_crc:#010x}")]
+    #[diagnostic(
+        code(espflash::verify_failed),
+        help("The data read back from flash offset {addr:#x} does not match what was written, the flash may be faulty or another process is writing to it")
+    )]
+    VerificationFailed {
+        addr: u32,
+        expected_crc: u32,
+        actual_crc: u32,
+    },
+    #[error("Flash integrity test failed at {addr:#x} (byte {offset:#x})")]
+    #[diagnostic(
+        code(espflash::flash_integrity),
+        help("Read back {actual:#04x} but wrote {expected:#04x} at flash offset {addr:#x}, the flash chip is likely faulty or counterfeit")
+    )]
+    FlashIntegrityError {
+        addr: u32,
+        offset: u32,
+        expected: u8,
+        actual: u8,
+    },
 }
 
 #[derive(Error, Debug, Diagnostic)]
@@ -101,6 +122,12 @@ pub enum ConnectionError {
         help("Try hard-resetting the device and try again, if the error persists your rom might be corrupted")
     )]
     OverSizedPacket,
+    #[error("IO error while using network transport: {0}")]
+    #[diagnostic(
+        code(espflash::network_error),
+        help("Ensure that the remote gateway is reachable and still exposing the device")
+    )]
+    NetworkError(#[source] io::Error),
 }
 
 #[derive(Debug, Default, Clone)]
@@ -298,6 +325,9 @@ pub enum PartitionTableError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     UnalignedPartitionError(#[from] UnalignedPartitionError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    BinaryParse(#[from] BinaryParseError),
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -467,6 +497,24 @@ impl UnalignedPartitionError {
     }
 }
 
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid partition table read from device: {reason}")]
+#[diagnostic(
+    code(espflash::partition_table::binary_parse),
+    help("The on-device partition table at 0x8000 is missing or corrupt, re-flash the table to recover")
+)]
+pub struct BinaryParseError {
+    reason: String,
+}
+
+impl BinaryParseError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        BinaryParseError {
+            reason: reason.into(),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("{0}")]
 pub struct ElfError(&'static str);
@@ -0,0 +1,179 @@
+use crate::chip::Chip;
+use crate::elf::{FirmwareImage, RomSegment};
+use crate::error::Error;
+use crate::image_format::{ImageFormat, ImageFormatId};
+use bytemuck::{bytes_of, Pod, Zeroable};
+use std::borrow::Cow;
+
+/// Start of the BL602 flash XIP window; the firmware segment is relocated
+/// against this address, the same way `blflash` does.
+const FLASH_ROM_START: u32 = 0x2300_0000;
+
+/// Flash offsets of the fixed image regions, mirroring the `blflash` layout.
+const BOOT_HEADER_ADDR: u32 = 0x0000;
+const PARTITION_TABLE_ADDR: u32 = 0xE000;
+const FIRMWARE_ADDR: u32 = 0x1_0000;
+
+/// Flash-configuration block, populated with the BL602 QIO defaults used by
+/// the eflash loader.
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct FlashConfig {
+    io_mode: u8,
+    continuous_read: u8,
+    clk_delay: u8,
+    clk_invert: u8,
+}
+
+impl Default for FlashConfig {
+    fn default() -> Self {
+        FlashConfig {
+            io_mode: 4, // QIO
+            continuous_read: 0,
+            clk_delay: 1,
+            clk_invert: 1,
+        }
+    }
+}
+
+/// Clock-configuration block, populated with the 40MHz XTAL / 160MHz PLL
+/// defaults.
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct ClockConfig {
+    xtal_type: u8,
+    pll_clk: u8,
+    hclk_div: u8,
+    bclk_div: u8,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        ClockConfig {
+            xtal_type: 4, // 40 MHz
+            pll_clk: 4,   // 160 MHz
+            hclk_div: 0,
+            bclk_div: 1,
+        }
+    }
+}
+
+/// BL602 boot header (`BFNP` magic) consumed by the first-stage eflash loader.
+///
+/// `crc32` is the last field and covers every preceding field, matching what
+/// the loader recomputes before accepting the header.
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct BootHeader {
+    magic: [u8; 4],
+    revision: u32,
+    flash_cfg: FlashConfig,
+    clk_cfg: ClockConfig,
+    boot_cfg: u32,
+    segment_count: u32,
+    entry: u32,
+    flash_offset: u32,
+    crc32: u32,
+}
+
+impl BootHeader {
+    fn new(entry: u32, flash_offset: u32) -> Self {
+        let mut header = BootHeader {
+            magic: *b"BFNP",
+            revision: 1,
+            flash_cfg: FlashConfig::default(),
+            clk_cfg: ClockConfig::default(),
+            boot_cfg: 0,
+            segment_count: 1,
+            entry,
+            flash_offset,
+            crc32: 0,
+        };
+
+        // the loader validates the crc over the header fields preceding it
+        let bytes = bytes_of(&header);
+        let mut crc = crate::verify::Crc32::new();
+        crc.update(&bytes[..bytes.len() - 4]);
+        header.crc32 = crc.finalize();
+        header
+    }
+}
+
+/// BL602 image format: boot header + partition table + XIP firmware.
+///
+/// Emits the same three-region layout the eflash-loader stub expects, with the
+/// firmware relocated against [`FLASH_ROM_START`], so the existing flashing
+/// workflow can program a Bouffalo BL602 the same way it does an ESP part.
+pub struct Bl602Format<'a> {
+    boot_header: RomSegment<'a>,
+    partition_table: RomSegment<'a>,
+    firmware: RomSegment<'a>,
+}
+
+impl<'a> Bl602Format<'a> {
+    pub fn new(image: &'a FirmwareImage) -> Result<Self, Error> {
+        let firmware_bytes = image.segments().fold(Vec::new(), |mut acc, segment| {
+            acc.extend_from_slice(segment.data());
+            acc
+        });
+
+        let boot_header = BootHeader::new(
+            image.entry().wrapping_add(FLASH_ROM_START),
+            FIRMWARE_ADDR,
+        );
+
+        Ok(Bl602Format {
+            boot_header: RomSegment {
+                addr: BOOT_HEADER_ADDR,
+                data: Cow::Owned(bytes_of(&boot_header).to_vec()),
+            },
+            partition_table: RomSegment {
+                addr: PARTITION_TABLE_ADDR,
+                data: Cow::Owned(partition_table()),
+            },
+            firmware: RomSegment {
+                addr: FIRMWARE_ADDR,
+                data: Cow::Owned(firmware_bytes),
+            },
+        })
+    }
+}
+
+impl<'a> ImageFormat<'a> for Bl602Format<'a> {
+    fn flash_segments<'b>(self) -> Box<dyn Iterator<Item = RomSegment<'b>> + 'b>
+    where
+        'a: 'b,
+    {
+        Box::new(
+            vec![self.boot_header, self.partition_table, self.firmware].into_iter(),
+        )
+    }
+
+    fn ota_segments<'b>(self) -> Box<dyn Iterator<Item = RomSegment<'b>> + 'b>
+    where
+        'a: 'b,
+    {
+        Box::new(vec![self.firmware].into_iter())
+    }
+}
+
+/// Minimal BL602 partition-table blob (`BFPT` magic) describing the single
+/// firmware partition at [`FIRMWARE_ADDR`].
+fn partition_table() -> Vec<u8> {
+    let mut table = Vec::new();
+    table.extend_from_slice(b"BFPT");
+    table.extend_from_slice(&1u32.to_le_bytes()); // entry count
+    table.extend_from_slice(&FIRMWARE_ADDR.to_le_bytes());
+    table
+}
+
+/// The image formats the BL602 understands, surfaced through
+/// `Chip::supported_image_formats`.
+pub const SUPPORTED_FORMATS: &[ImageFormatId] = &[ImageFormatId::Bl602];
+
+/// Detection helper: the BL602 ROM reports this magic word at connect time.
+pub const BL602_MAGIC: u32 = 0x4345_3000;
+
+pub fn is_bl602(magic: u32) -> Option<Chip> {
+    (magic == BL602_MAGIC).then(|| Chip::Bl602)
+}
@@ -0,0 +1,29 @@
+use crate::elf::RomSegment;
+use strum::{AsStaticStr, EnumVariantNames};
+
+pub mod bl602;
+
+/// Identifier for the on-flash image layout emitted for a chip.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, AsStaticStr, EnumVariantNames)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ImageFormatId {
+    /// Standard second-stage bootloader layout used by the ESP parts.
+    Bootloader,
+    /// ESP32-C3 direct-boot layout.
+    DirectBoot,
+    /// Bouffalo BL602 boot-header + partition-table layout.
+    Bl602,
+}
+
+/// An image format knows how to turn a parsed firmware image into the set of
+/// [`RomSegment`]s written to flash.
+pub trait ImageFormat<'a> {
+    /// The segments written when flashing the whole image.
+    fn flash_segments<'b>(self) -> Box<dyn Iterator<Item = RomSegment<'b>> + 'b>
+    where
+        'a: 'b;
+    /// The segments written for an OTA update (firmware only).
+    fn ota_segments<'b>(self) -> Box<dyn Iterator<Item = RomSegment<'b>> + 'b>
+    where
+        'a: 'b;
+}
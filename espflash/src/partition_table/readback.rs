@@ -0,0 +1,170 @@
+use crate::error::{BinaryParseError, Error, PartitionTableError};
+use crate::flasher::Flasher;
+use crate::partition_table::PartitionTable;
+use md5::{Digest, Md5};
+
+/// Standard flash offset of the partition table.
+pub const PARTITION_TABLE_OFFSET: u32 = 0x8000;
+/// Maximum size of the partition table region.
+const PARTITION_TABLE_SIZE: u32 = 0xC00;
+/// Size of a single partition entry.
+const ENTRY_SIZE: usize = 32;
+/// Magic word prefixing every partition entry (bytes `0xAA 0x50`, read
+/// little-endian).
+const ENTRY_MAGIC: u16 = 0x50AA;
+/// Magic word prefixing the trailing md5 checksum entry.
+const MD5_MAGIC: u16 = 0xEBEB;
+
+/// Read the partition table back from flash and parse it.
+///
+/// Reads [`PARTITION_TABLE_SIZE`] bytes from [`PARTITION_TABLE_OFFSET`], parses
+/// the binary entries and re-renders them as the CSV the crate already
+/// understands, then re-runs the normal validation (overlap/duplicate/alignment
+/// /subtype checks) against the recovered table so a corrupt deployed layout is
+/// reported the same way an invalid input would be.
+pub fn read_partition_table(flasher: &mut Flasher) -> Result<PartitionTable, Error> {
+    let raw = flasher.read_flash(PARTITION_TABLE_OFFSET, PARTITION_TABLE_SIZE)?;
+    let csv = binary_to_csv(&raw)?;
+    // Reuse the existing CSV validation against the recovered table.
+    let table = PartitionTable::try_from_str(&csv)?;
+    Ok(table)
+}
+
+/// Parse the binary partition table into the crate's CSV representation.
+fn binary_to_csv(raw: &[u8]) -> Result<String, PartitionTableError> {
+    let mut csv = String::from("# Name, Type, SubType, Offset, Size, Flags\n");
+    let mut checksum = Md5::new();
+    let mut seen_entry = false;
+
+    for chunk in raw.chunks_exact(ENTRY_SIZE) {
+        let magic = u16::from_le_bytes([chunk[0], chunk[1]]);
+        match magic {
+            ENTRY_MAGIC => {
+                checksum.update(chunk);
+                csv.push_str(&entry_to_csv(chunk)?);
+                csv.push('\n');
+                seen_entry = true;
+            }
+            MD5_MAGIC => {
+                let expected = &chunk[16..32];
+                let actual = checksum.finalize_reset();
+                if expected != actual.as_slice() {
+                    return Err(BinaryParseError::new("partition table checksum mismatch").into());
+                }
+                return Ok(csv);
+            }
+            0xFFFF => break, // unwritten flash padding marks the end of the table
+            _ => {
+                return Err(BinaryParseError::new(format!(
+                    "unexpected entry magic {:#06x}",
+                    magic
+                ))
+                .into())
+            }
+        }
+    }
+
+    if !seen_entry {
+        return Err(BinaryParseError::new("no partition entries found").into());
+    }
+
+    Ok(csv)
+}
+
+/// Render a single 32-byte binary entry as one CSV row.
+fn entry_to_csv(entry: &[u8]) -> Result<String, PartitionTableError> {
+    let ty = entry[2];
+    let subtype = entry[3];
+    let offset = u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]);
+    let size = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]);
+    let label_end = entry[12..28].iter().position(|&b| b == 0).unwrap_or(16);
+    let label = std::str::from_utf8(&entry[12..12 + label_end])
+        .map_err(|_| BinaryParseError::new("partition label is not valid utf-8"))?;
+
+    Ok(format!(
+        "{}, {}, {}, {:#x}, {:#x},",
+        label,
+        type_name(ty),
+        subtype_name(ty, subtype),
+        offset,
+        size
+    ))
+}
+
+/// Map a binary partition type back to the token the CSV deserializer accepts.
+fn type_name(ty: u8) -> String {
+    match ty {
+        0x00 => "app".to_string(),
+        0x01 => "data".to_string(),
+        other => format!("{:#04x}", other),
+    }
+}
+
+/// Map a binary partition subtype back to the token the CSV deserializer
+/// accepts, given its type.
+fn subtype_name(ty: u8, subtype: u8) -> String {
+    match (ty, subtype) {
+        (0x00, 0x00) => "factory".to_string(),
+        (0x00, s @ 0x10..=0x1f) => format!("ota_{}", s - 0x10),
+        (0x00, 0x20) => "test".to_string(),
+        (0x01, 0x00) => "ota".to_string(),
+        (0x01, 0x01) => "phy".to_string(),
+        (0x01, 0x02) => "nvs".to_string(),
+        (0x01, 0x03) => "coredump".to_string(),
+        (0x01, 0x04) => "nvs_keys".to_string(),
+        (0x01, 0x05) => "efuse".to_string(),
+        (0x01, 0x81) => "fat".to_string(),
+        (0x01, 0x82) => "spiffs".to_string(),
+        (_, other) => format!("{:#04x}", other),
+    }
+}
+
+/// Read the contents of a single partition by name.
+///
+/// Modelled on the ARTIQ config read/write/erase interface, this operates on one
+/// named partition rather than re-flashing the whole table.
+pub fn read_partition(flasher: &mut Flasher, name: &str) -> Result<Vec<u8>, Error> {
+    let part = find_partition(flasher, name)?;
+    let data = flasher.read_flash(part.offset, part.size)?;
+    Ok(data)
+}
+
+/// Write `data` to a single partition by name, erasing it first.
+pub fn write_partition(flasher: &mut Flasher, name: &str, data: &[u8]) -> Result<(), Error> {
+    let part = find_partition(flasher, name)?;
+    if data.len() as u32 > part.size {
+        return Err(BinaryParseError::new(format!(
+            "data ({} bytes) does not fit in partition '{}' ({} bytes)",
+            data.len(),
+            name,
+            part.size
+        ))
+        .into());
+    }
+    flasher.erase_region(part.offset, part.size)?;
+    flasher.write_bin_to_flash(part.offset, data)?;
+    Ok(())
+}
+
+/// Erase a single partition by name.
+pub fn erase_partition(flasher: &mut Flasher, name: &str) -> Result<(), Error> {
+    let part = find_partition(flasher, name)?;
+    flasher.erase_region(part.offset, part.size)?;
+    Ok(())
+}
+
+struct PartitionRegion {
+    offset: u32,
+    size: u32,
+}
+
+fn find_partition(flasher: &mut Flasher, name: &str) -> Result<PartitionRegion, Error> {
+    let table = read_partition_table(flasher)?;
+    let partition = table
+        .find(name)
+        .ok_or_else(|| BinaryParseError::new(format!("no partition named '{}'", name)))?;
+    Ok(PartitionRegion {
+        offset: partition.offset(),
+        size: partition.size(),
+    })
+}
@@ -0,0 +1,182 @@
+use crate::error::{ConnectionError, TimedOutCommand};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use bytes::BytesMut;
+use serial::{BaudRate, SerialPort, SystemPort};
+use slip_codec::SlipDecoder;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Map a network IO error onto a [`ConnectionError`].
+///
+/// A read timeout is surfaced as [`ConnectionError::Timeout`] so that
+/// `ResultExt::for_command` can still attribute it to the `Command` in flight,
+/// exactly as it does for the serial transport; everything else is a
+/// [`ConnectionError::NetworkError`].
+fn network_error(err: io::Error) -> ConnectionError {
+    match err.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => {
+            ConnectionError::Timeout(TimedOutCommand::default())
+        }
+        _ => ConnectionError::NetworkError(err),
+    }
+}
+
+/// Largest firmware payload carried in a single remote-flash chunk.
+///
+/// Mirrors the DRTIO remote-flash packet scheme: the image is split into fixed
+/// size payloads and each is acknowledged before the next is sent.
+pub const MASTER_PAYLOAD_MAX_SIZE: usize = 1024;
+
+/// Abstraction over the physical link used to reach a device.
+///
+/// The serial port is one implementation; a [`NetworkTransport`] lets a board
+/// exposed over a remote gateway be flashed without a local UART.
+pub trait Transport {
+    /// Read a single framed packet from the device.
+    fn read_packet(&mut self, timeout: Duration) -> Result<Vec<u8>, ConnectionError>;
+    /// Write a single framed packet to the device.
+    fn write_packet(&mut self, bytes: &[u8]) -> Result<(), ConnectionError>;
+    /// Pulse the reset line to restart the device.
+    fn reset(&mut self) -> Result<(), ConnectionError>;
+    /// Change the link speed once the higher baud has been negotiated.
+    fn set_baud(&mut self, speed: u32) -> Result<(), ConnectionError>;
+}
+
+/// [`Transport`] backed by a local serial port.
+pub struct SerialTransport {
+    port: SystemPort,
+}
+
+impl SerialTransport {
+    pub fn new(port: SystemPort) -> Self {
+        SerialTransport { port }
+    }
+}
+
+impl Transport for SerialTransport {
+    fn read_packet(&mut self, timeout: Duration) -> Result<Vec<u8>, ConnectionError> {
+        self.port.set_timeout(timeout)?;
+        // a serial port never hits EOF, so read one SLIP-framed packet rather
+        // than draining to the end of the stream
+        let mut decoder = SlipDecoder::new();
+        let mut buf = BytesMut::new();
+        decoder.decode(&mut self.port, &mut buf)?;
+        Ok(buf.to_vec())
+    }
+
+    fn write_packet(&mut self, bytes: &[u8]) -> Result<(), ConnectionError> {
+        self.port.write_all(bytes)?;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), ConnectionError> {
+        self.port.set_dtr(false)?;
+        self.port.set_rts(true)?;
+        self.port.set_rts(false)?;
+        Ok(())
+    }
+
+    fn set_baud(&mut self, speed: u32) -> Result<(), ConnectionError> {
+        self.port
+            .reconfigure(&|settings| settings.set_baud_rate(BaudRate::from_speed(speed as usize)))?;
+        Ok(())
+    }
+}
+
+/// [`Transport`] backed by a TCP connection to a remote flashing gateway.
+///
+/// Writes follow the DRTIO remote-flash scheme: the firmware is split into
+/// [`MASTER_PAYLOAD_MAX_SIZE`] payloads, each sent as
+/// `{ last: bool, length: u16, data: [..] }` and acknowledged by the gateway
+/// before the next chunk is sent, so a failed transfer can report exactly which
+/// chunk was rejected.
+pub struct NetworkTransport {
+    stream: TcpStream,
+}
+
+impl NetworkTransport {
+    pub fn connect(addr: &str) -> Result<Self, ConnectionError> {
+        let stream = TcpStream::connect(addr).map_err(ConnectionError::NetworkError)?;
+        Ok(NetworkTransport { stream })
+    }
+
+    /// Stream `image` to the gateway one acknowledged chunk at a time.
+    ///
+    /// Returns the number of chunks written, or a [`ConnectionError`] naming the
+    /// chunk index that the gateway failed to acknowledge.
+    pub fn write_image(&mut self, image: &[u8]) -> Result<usize, ConnectionError> {
+        let mut chunk = 0;
+        let mut sent = 0;
+
+        while sent < image.len() {
+            let len = MASTER_PAYLOAD_MAX_SIZE.min(image.len() - sent);
+            let last = sent + len == image.len();
+
+            let mut frame = Vec::with_capacity(len + 3);
+            frame.push(last as u8);
+            frame
+                .write_u16::<LittleEndian>(len as u16)
+                .map_err(ConnectionError::NetworkError)?;
+            frame.extend_from_slice(&image[sent..sent + len]);
+
+            self.stream
+                .write_all(&frame)
+                .map_err(ConnectionError::NetworkError)?;
+            self.await_ack(chunk)?;
+
+            sent += len;
+            chunk += 1;
+        }
+
+        Ok(chunk)
+    }
+
+    /// Block for the per-chunk reply and map a rejection onto the chunk index.
+    fn await_ack(&mut self, chunk: usize) -> Result<(), ConnectionError> {
+        let status = self.stream.read_u8().map_err(network_error)?;
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(ConnectionError::NetworkError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("remote gateway rejected chunk {} (status {})", chunk, status),
+            )))
+        }
+    }
+}
+
+impl Transport for NetworkTransport {
+    fn read_packet(&mut self, timeout: Duration) -> Result<Vec<u8>, ConnectionError> {
+        self.stream
+            .set_read_timeout(Some(timeout))
+            .map_err(network_error)?;
+        let len = self.stream.read_u16::<LittleEndian>().map_err(network_error)? as usize;
+        let mut buf = vec![0; len];
+        self.stream.read_exact(&mut buf).map_err(network_error)?;
+        Ok(buf)
+    }
+
+    fn write_packet(&mut self, bytes: &[u8]) -> Result<(), ConnectionError> {
+        self.write_image(bytes).map(|_| ())
+    }
+
+    fn reset(&mut self) -> Result<(), ConnectionError> {
+        // command byte 0x02 asks the gateway to pulse the reset line
+        self.stream
+            .write_all(&[0x02])
+            .map_err(ConnectionError::NetworkError)?;
+        self.await_ack(0)
+    }
+
+    fn set_baud(&mut self, speed: u32) -> Result<(), ConnectionError> {
+        let mut frame = vec![0x03];
+        frame
+            .write_u32::<LittleEndian>(speed)
+            .map_err(ConnectionError::NetworkError)?;
+        self.stream
+            .write_all(&frame)
+            .map_err(ConnectionError::NetworkError)?;
+        self.await_ack(0)
+    }
+}
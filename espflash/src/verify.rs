@@ -0,0 +1,84 @@
+use crate::elf::RomSegment;
+use crate::error::Error;
+use crate::flasher::Flasher;
+
+/// Size of the read-back chunks pulled from flash while verifying.
+///
+/// We fold the CRC incrementally over chunks of this size so the whole image
+/// never has to be buffered at once.
+const VERIFY_CHUNK_SIZE: usize = 4096;
+
+/// Running CRC-32 as used by `blflash`'s read-back check.
+///
+/// Poly `0x04C11DB7`, reflected, initial value `0xFFFFFFFF`, final XOR
+/// `0xFFFFFFFF` — i.e. the same parameters as zlib's `crc32`.
+#[derive(Debug, Clone)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Crc32 { state: u32::MAX }
+    }
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Crc32::default()
+    }
+
+    /// Fold another run of bytes into the running checksum.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u32;
+            for _ in 0..8 {
+                let mask = (self.state & 1).wrapping_neg();
+                self.state = (self.state >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    /// Consume the running state and produce the final checksum.
+    pub fn finalize(self) -> u32 {
+        self.state ^ u32::MAX
+    }
+}
+
+/// Read `segment` back from flash and confirm it matches what was written.
+///
+/// The CRC is computed incrementally over 4KB read-back chunks, so verifying a
+/// large image doesn't require holding the whole read-back in memory. On a
+/// mismatch an [`Error::VerificationFailed`] carrying both checksums is
+/// returned, naming the flash offset at which the region starts.
+pub fn verify_segment(flasher: &mut Flasher, segment: &RomSegment) -> Result<(), Error> {
+    let addr = segment.addr;
+    let data = segment.data.as_ref();
+
+    let mut expected = Crc32::new();
+    let mut actual = Crc32::new();
+
+    let mut offset = 0;
+    while offset < data.len() {
+        let len = VERIFY_CHUNK_SIZE.min(data.len() - offset);
+        let read_back = flasher.read_flash(addr + offset as u32, len as u32)?;
+
+        expected.update(&data[offset..offset + len]);
+        actual.update(&read_back);
+
+        offset += len;
+    }
+
+    let expected_crc = expected.finalize();
+    let actual_crc = actual.finalize();
+
+    if expected_crc != actual_crc {
+        return Err(Error::VerificationFailed {
+            addr,
+            expected_crc,
+            actual_crc,
+        });
+    }
+
+    Ok(())
+}